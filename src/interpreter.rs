@@ -0,0 +1,66 @@
+use crate::ast::{BinaryOp, Expr, UnaryOp, Value};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RuntimeError {
+    #[error("Operand must be a number.")]
+    OperandMustBeNumber,
+    #[error("Operands must be numbers.")]
+    OperandsMustBeNumbers,
+    #[error("Operands must be two numbers or two strings.")]
+    OperandsMustBeNumbersOrStrings,
+}
+
+pub type Result<T> = std::result::Result<T, RuntimeError>;
+
+/// Tree-walking evaluator: recursively reduces an [`Expr`] to a [`Value`].
+pub fn evaluate(expr: &Expr) -> Result<Value> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Grouping(inner) => evaluate(inner),
+        Expr::Unary { op, rhs } => {
+            let rhs = evaluate(rhs)?;
+            match op {
+                UnaryOp::Not => Ok(Value::Bool(!rhs.is_truthy())),
+                UnaryOp::Negate => match rhs {
+                    Value::Number(n) => Ok(Value::Number(-n)),
+                    _ => Err(RuntimeError::OperandMustBeNumber),
+                },
+            }
+        }
+        Expr::Binary { op, lhs, rhs } => {
+            let lhs = evaluate(lhs)?;
+            let rhs = evaluate(rhs)?;
+            match op {
+                BinaryOp::Equal => Ok(Value::Bool(lhs == rhs)),
+                BinaryOp::NotEqual => Ok(Value::Bool(lhs != rhs)),
+                BinaryOp::Add => match (lhs, rhs) {
+                    (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+                    (Value::Str(a), Value::Str(b)) => Ok(Value::Str(a + &b)),
+                    _ => Err(RuntimeError::OperandsMustBeNumbersOrStrings),
+                },
+                BinaryOp::Subtract => arithmetic(lhs, rhs, |a, b| a - b),
+                BinaryOp::Multiply => arithmetic(lhs, rhs, |a, b| a * b),
+                BinaryOp::Divide => arithmetic(lhs, rhs, |a, b| a / b),
+                BinaryOp::Less => compare(lhs, rhs, |a, b| a < b),
+                BinaryOp::LessEqual => compare(lhs, rhs, |a, b| a <= b),
+                BinaryOp::Greater => compare(lhs, rhs, |a, b| a > b),
+                BinaryOp::GreaterEqual => compare(lhs, rhs, |a, b| a >= b),
+            }
+        }
+    }
+}
+
+fn arithmetic(lhs: Value, rhs: Value, op: impl Fn(f64, f64) -> f64) -> Result<Value> {
+    match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => Ok(Value::Number(op(a, b))),
+        _ => Err(RuntimeError::OperandsMustBeNumbers),
+    }
+}
+
+fn compare(lhs: Value, rhs: Value, op: impl Fn(f64, f64) -> bool) -> Result<Value> {
+    match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => Ok(Value::Bool(op(a, b))),
+        _ => Err(RuntimeError::OperandsMustBeNumbers),
+    }
+}
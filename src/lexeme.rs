@@ -1,5 +1,6 @@
 use std::fmt;
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum MathOp {
     Plus,
     Minus,
@@ -18,13 +19,16 @@ impl fmt::Display for MathOp {
     }
 }
 
-pub enum Lexeme {
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lexeme<'a> {
     Eof,
-    Identifier(String),
-    Number(String, f64),
-    String(String),
+    Identifier(&'a str),
+    Number(&'a str, f64),
+    // Borrows the source form for display; owns the decoded value because
+    // escape sequences need not appear contiguously in the source.
+    String(&'a str, String),
     Operator(MathOp),
-    Keyword(String),
+    Keyword(&'a str),
     LeftParen,
     RightParen,
     LeftBrace,
@@ -43,9 +47,11 @@ pub enum Lexeme {
     // errors
     UnexpectedCharError(usize, char),
     UnterminatedStringError(usize),
+    MalformedEscapeError(usize, String),
+    MalformedNumberError(usize, String),
 }
 
-impl fmt::Display for Lexeme {
+impl fmt::Display for Lexeme<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Lexeme::Eof => write!(f, "EOF  null"),
@@ -57,7 +63,7 @@ impl fmt::Display for Lexeme {
                     write!(f, "NUMBER {} {}", original, n)
                 }
             }
-            Lexeme::String(s) => write!(f, "STRING \"{}\" {}", s, s),
+            Lexeme::String(original, value) => write!(f, "STRING \"{}\" {}", original, value),
             Lexeme::Operator(op) => write!(f, "{} null", op),
             Lexeme::Keyword(kw) => write!(f, "{} {} null", kw.to_uppercase(), kw),
             Lexeme::LeftParen => write!(f, "LEFT_PAREN ( null"),
@@ -76,12 +82,18 @@ impl fmt::Display for Lexeme {
             Lexeme::Greater => write!(f, "GREATER > null"),
             Lexeme::GreaterEqual => write!(f, "GREATER_EQUAL >= null"),
             // errors
-            Lexeme::UnexpectedCharError(line, ch) => {
-                write!(f, "[line {}] Error: Unexpected character: {}", line, ch)
+            Lexeme::UnexpectedCharError(_, ch) => {
+                write!(f, "Error: Unexpected character: {}", ch)
             }
-            Lexeme::UnterminatedStringError(line) => {
-                write!(f, "[line {}] Error: Unterminated string.", line)
+            Lexeme::UnterminatedStringError(_) => {
+                write!(f, "Error: Unterminated string.")
+            }
+            Lexeme::MalformedEscapeError(_, msg) => {
+                write!(f, "Error: {}", msg)
+            }
+            Lexeme::MalformedNumberError(_, original) => {
+                write!(f, "Error: Malformed number: {}", original)
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -3,12 +3,16 @@ use std::{fs, path::PathBuf, process};
 use clap::{Parser, Subcommand};
 use thiserror::Error;
 
+mod ast;
+mod interpreter;
 mod lexeme;
 mod parser;
+mod token;
 mod tokenizer;
 
 use lexeme::Lexeme;
-use parser::{Parser as LoxParser, ParserError};
+use parser::Parser as LoxParser;
+use token::Token;
 use tokenizer::Tokenizer;
 
 #[derive(Parser)]
@@ -28,6 +32,10 @@ enum Commands {
         #[arg(value_name = "FILE", help = "Path to the source file")]
         file: PathBuf,
     },
+    Evaluate {
+        #[arg(value_name = "FILE", help = "Path to the source file")]
+        file: PathBuf,
+    },
 }
 
 #[derive(Error, Debug)]
@@ -36,8 +44,6 @@ enum AppError {
     FileRead(#[from] std::io::Error),
     #[error("Tokenization error")]
     Tokenization,
-    #[error("Parsing error: {0}")]
-    Parsing(#[from] ParserError),
 }
 
 fn main() -> Result<(), AppError> {
@@ -46,6 +52,7 @@ fn main() -> Result<(), AppError> {
     match &cli.command {
         Commands::Tokenize { file } => tokenize_file(file)?,
         Commands::Parse { file } => parse_file(file)?,
+        Commands::Evaluate { file } => evaluate_file(file)?,
     }
 
     Ok(())
@@ -66,17 +73,20 @@ fn tokenize_file(file: &PathBuf) -> Result<(), AppError> {
     Ok(())
 }
 
-fn print_tokens(tokens: &[Lexeme]) {
+fn print_tokens(tokens: &[Token<'_>]) {
     for token in tokens {
         println!("{}", token);
     }
 }
 
-fn print_tokens_with_errors(tokens: &[Lexeme]) {
+fn print_tokens_with_errors(tokens: &[Token<'_>]) {
     for token in tokens {
-        match token {
-            Lexeme::UnexpectedCharError(..) | Lexeme::UnterminatedStringError(..) => {
-                eprintln!("{}", token)
+        match token.lexeme {
+            Lexeme::UnexpectedCharError(..)
+            | Lexeme::UnterminatedStringError(..)
+            | Lexeme::MalformedEscapeError(..)
+            | Lexeme::MalformedNumberError(..) => {
+                eprintln!("{} {}", token.span, token.lexeme)
             }
             _ => println!("{}", token),
         }
@@ -92,9 +102,44 @@ fn parse_file(file: &PathBuf) -> Result<(), AppError> {
         .map_err(|_| AppError::Tokenization)?;
     let mut parser = LoxParser::new(tokens);
 
-    match parser.parse() {
-        Ok(result) => println!("{}", result),
-        Err(err) => return Err(AppError::Parsing(err)),
+    match parser.parse_all() {
+        Ok(expressions) => {
+            for expr in expressions {
+                println!("{}", expr);
+            }
+        }
+        Err(errors) => {
+            for err in errors {
+                eprintln!("{}", err);
+            }
+            process::exit(65);
+        }
+    }
+
+    Ok(())
+}
+
+fn evaluate_file(file: &PathBuf) -> Result<(), AppError> {
+    let file_contents = fs::read_to_string(file).map_err(AppError::FileRead)?;
+    let mut tokenizer = Tokenizer::new(&file_contents);
+
+    let tokens = tokenizer.tokenize().map_err(|_| AppError::Tokenization)?;
+    let mut parser = LoxParser::new(tokens);
+
+    let expr = match parser.parse() {
+        Ok(expr) => expr,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(65);
+        }
+    };
+
+    match interpreter::evaluate(&expr) {
+        Ok(value) => println!("{}", value),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(70);
+        }
     }
 
     Ok(())
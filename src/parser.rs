@@ -1,191 +1,167 @@
+use crate::ast::{BinaryOp, Expr, UnaryOp, Value};
 use crate::lexeme::{Lexeme, MathOp};
+use crate::token::{Span, Token};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
-pub enum ParserError {
-    #[error("Unexpected token: {0:?}")]
-    UnexpectedToken(Lexeme),
-    #[error("Unmatched parentheses")]
-    UnmatchedParentheses,
-    #[error("Expected token: {0:?}")]
-    ExpectedToken(Lexeme),
-    #[error("Empty grouping")]
-    EmptyGrouping,
-    #[error("Invalid unary operator: {0:?}")]
-    InvalidUnaryOperator(Lexeme),
+pub enum ParserError<'a> {
+    #[error("{1} Unexpected token: {0:?}")]
+    UnexpectedToken(Lexeme<'a>, Span),
+    #[error("{0} Unmatched parentheses")]
+    UnmatchedParentheses(Span),
+    #[error("{1} Expected token: {0:?}")]
+    ExpectedToken(Lexeme<'a>, Span),
+    #[error("{0} Empty grouping")]
+    EmptyGrouping(Span),
+    #[error("{1} Invalid unary operator: {0:?}")]
+    InvalidUnaryOperator(Lexeme<'a>, Span),
 }
 
-pub type Result<T> = std::result::Result<T, ParserError>;
+pub type Result<'a, T> = std::result::Result<T, ParserError<'a>>;
 
 pub struct Parser<'a> {
-    tokens: &'a [Lexeme],
+    tokens: &'a [Token<'a>],
     current: usize,
 }
 
 impl<'a> Parser<'a> {
-    pub fn new(tokens: &'a [Lexeme]) -> Self {
+    pub fn new(tokens: &'a [Token<'a>]) -> Self {
         Parser { tokens, current: 0 }
     }
 
-    pub fn parse(&mut self) -> Result<String> {
-        let mut output = String::new();
-        while !self.is_at_end() {
-            output.push_str(&self.parse_expression()?);
-            output.push('\n');
-        }
-        Ok(output)
-    }
-
-    fn parse_expression(&mut self) -> Result<String> {
-        self.parse_equality()
+    pub fn parse(&mut self) -> Result<'a, Expr> {
+        self.parse_expression(0)
     }
 
-    fn parse_equality(&mut self) -> Result<String> {
-        let mut expr = self.parse_comparison()?;
+    /// Parses every top-level expression in the input, recovering from a
+    /// `ParserError` by synchronizing to the next statement boundary and
+    /// carrying on. Returns all successfully parsed expressions, or every
+    /// diagnostic collected along the way if any error occurred.
+    pub fn parse_all(&mut self) -> std::result::Result<Vec<Expr>, Vec<ParserError<'a>>> {
+        let mut expressions = Vec::new();
+        let mut errors = Vec::new();
 
-        while matches!(self.peek(), Lexeme::EqualEqual | Lexeme::BangEqual) {
-            let operator = self.advance().clone();
-            let right = self.parse_comparison()?;
-            expr = match operator {
-                Lexeme::EqualEqual => format!("(== {} {})", expr, right),
-                Lexeme::BangEqual => format!("(!= {} {})", expr, right),
-                _ => unreachable!(),
-            };
+        while !self.is_at_end() {
+            match self.parse_expression(0) {
+                Ok(expr) => expressions.push(expr),
+                Err(err) => {
+                    errors.push(err);
+                    self.synchronize();
+                }
+            }
         }
 
-        Ok(expr)
-    }
-
-    fn parse_comparison(&mut self) -> Result<String> {
-        let mut expr = self.parse_term()?;
-
-        while matches!(
-            self.peek(),
-            Lexeme::Greater | Lexeme::Less | Lexeme::GreaterEqual | Lexeme::LessEqual
-        ) {
-            let operator = self.advance().clone();
-            let right = self.parse_term()?;
-            expr = match operator {
-                Lexeme::Greater => format!("(> {} {})", expr, right),
-                Lexeme::Less => format!("(< {} {})", expr, right),
-                Lexeme::GreaterEqual => format!("(>= {} {})", expr, right),
-                Lexeme::LessEqual => format!("(<= {} {})", expr, right),
-                _ => unreachable!(),
-            };
+        if errors.is_empty() {
+            Ok(expressions)
+        } else {
+            Err(errors)
         }
-
-        Ok(expr)
     }
 
-    fn parse_term(&mut self) -> Result<String> {
-        let mut expr = self.parse_factor()?;
-
-        while matches!(
-            self.peek(),
-            Lexeme::Operator(MathOp::Plus) | Lexeme::Operator(MathOp::Minus)
-        ) {
-            let operator = self.advance().clone();
-            let right = self.parse_factor()?;
-            expr = match operator {
-                Lexeme::Operator(MathOp::Plus) => format!("(+ {} {})", expr, right),
-                Lexeme::Operator(MathOp::Minus) => format!("(- {} {})", expr, right),
-                _ => unreachable!(),
-            };
+    /// Discards tokens until a likely statement boundary so parsing can resume
+    /// after an error: just past the next `;`, or at the start of a statement
+    /// keyword.
+    fn synchronize(&mut self) {
+        self.advance();
+        while !self.is_at_end() {
+            if matches!(self.previous(), Lexeme::Semicolon) {
+                return;
+            }
+            if let Lexeme::Keyword(kw) = self.peek() {
+                if is_statement_start(kw) {
+                    return;
+                }
+            }
+            self.advance();
         }
-
-        Ok(expr)
     }
 
-    fn parse_factor(&mut self) -> Result<String> {
-        let mut expr = self.parse_unary()?;
+    /// Precedence-climbing (Pratt) expression parser: parses a prefix/nullary
+    /// `lhs`, then folds in every following infix operator whose left binding
+    /// power is at least `min_bp`, recursing with the operator's right binding
+    /// power. Precedence lives entirely in the binding-power tables below, so a
+    /// new infix operator needs a table entry and an `infix_op` arm rather than
+    /// a new method.
+    fn parse_expression(&mut self, min_bp: u8) -> Result<'a, Expr> {
+        let mut lhs = self.parse_prefix()?;
 
-        while matches!(
-            self.peek(),
-            Lexeme::Operator(MathOp::Star) | Lexeme::Operator(MathOp::Slash)
-        ) {
+        while let Some((l_bp, r_bp)) = infix_binding_power(self.peek()) {
+            if l_bp < min_bp {
+                break;
+            }
             let operator = self.advance().clone();
-            let right = self.parse_unary()?;
-            expr = match operator {
-                Lexeme::Operator(MathOp::Star) => format!("(* {} {})", expr, right),
-                Lexeme::Operator(MathOp::Slash) => format!("(/ {} {})", expr, right),
-                _ => unreachable!(),
+            let rhs = self.parse_expression(r_bp)?;
+            lhs = Expr::Binary {
+                op: infix_op(&operator),
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
             };
         }
 
-        Ok(expr)
+        Ok(lhs)
     }
 
-    fn parse_unary(&mut self) -> Result<String> {
+    fn parse_prefix(&mut self) -> Result<'a, Expr> {
         match self.peek() {
             Lexeme::Bang | Lexeme::Operator(MathOp::Minus) => {
                 let operator = self.advance().clone();
-                let right = self.parse_unary()?;
-                match operator {
-                    Lexeme::Bang => Ok(format!("(! {})", right)),
-                    Lexeme::Operator(MathOp::Minus) => Ok(format!("(- {})", right)),
-                    _ => Err(ParserError::InvalidUnaryOperator(operator)),
-                }
+                let span = self.previous_span();
+                let ((), r_bp) = prefix_binding_power(&operator);
+                let rhs = self.parse_expression(r_bp)?;
+                let op = match operator {
+                    Lexeme::Bang => UnaryOp::Not,
+                    Lexeme::Operator(MathOp::Minus) => UnaryOp::Negate,
+                    _ => return Err(ParserError::InvalidUnaryOperator(operator, span)),
+                };
+                Ok(Expr::Unary {
+                    op,
+                    rhs: Box::new(rhs),
+                })
             }
             Lexeme::LeftParen => self.parse_grouping(),
             _ => self.parse_literal(),
         }
     }
 
-    fn parse_grouping(&mut self) -> Result<String> {
+    fn parse_grouping(&mut self) -> Result<'a, Expr> {
         self.advance();
-        let expressions = self.parse_grouped_expressions()?;
-        if expressions.is_empty() {
-            return Err(ParserError::EmptyGrouping);
+        let group_span = self.previous_span();
+        if self.peek() == &Lexeme::RightParen {
+            return Err(ParserError::EmptyGrouping(group_span));
         }
+        let expr = self.parse_expression(0)?;
         self.consume(Lexeme::RightParen)?;
-        Ok(format!("(group {})", expressions.join(", ")))
+        Ok(Expr::Grouping(Box::new(expr)))
     }
 
-    fn parse_grouped_expressions(&mut self) -> Result<Vec<String>> {
-        let mut expressions = Vec::new();
-
-        loop {
-            match self.peek() {
-                Lexeme::RightParen => break,
-                Lexeme::Eof => return Err(ParserError::UnmatchedParentheses),
-                _ => {
-                    expressions.push(self.parse_expression()?);
-                    if self.peek() != &Lexeme::Comma {
-                        break;
-                    }
-                    self.advance(); // consume the comma to avoid trailing comma
-                }
-            }
-        }
-
-        Ok(expressions)
-    }
-
-    fn parse_literal(&mut self) -> Result<String> {
+    fn parse_literal(&mut self) -> Result<'a, Expr> {
+        let span = self.peek_span();
         match self.advance() {
-            Lexeme::Keyword(s) => Ok(match s.as_str() {
-                "true" | "false" | "nil" => s.to_string(),
-                _ => "unknown".to_string(),
-            }),
-            Lexeme::Number(_, n) => Ok(if n.fract() == 0.0 {
-                format!("{:.1}", n)
-            } else {
-                n.to_string()
-            }),
-            Lexeme::String(s) => Ok(s.to_string()),
-            unexpected => Err(ParserError::UnexpectedToken(unexpected.clone())),
+            Lexeme::Keyword(s) => match *s {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "nil" => Ok(Expr::Literal(Value::Nil)),
+                _ => Err(ParserError::UnexpectedToken(Lexeme::Keyword(*s), span)),
+            },
+            Lexeme::Number(_, n) => Ok(Expr::Literal(Value::Number(*n))),
+            Lexeme::String(_, value) => Ok(Expr::Literal(Value::Str(value.clone()))),
+            unexpected => Err(ParserError::UnexpectedToken(unexpected.clone(), span)),
         }
     }
 
-    fn consume(&mut self, expected: Lexeme) -> Result<&Lexeme> {
+    fn consume(&mut self, expected: Lexeme<'a>) -> Result<'a, ()> {
+        let span = self.peek_span();
         match self.peek() {
-            lexeme if lexeme == &expected => Ok(self.advance()),
-            Lexeme::Eof => Err(ParserError::UnmatchedParentheses),
-            _ => Err(ParserError::ExpectedToken(expected)),
+            lexeme if lexeme == &expected => {
+                self.advance();
+                Ok(())
+            }
+            Lexeme::Eof => Err(ParserError::UnmatchedParentheses(span)),
+            _ => Err(ParserError::ExpectedToken(expected, span)),
         }
     }
 
-    fn advance(&mut self) -> &Lexeme {
+    fn advance(&mut self) -> &Lexeme<'a> {
         if !self.is_at_end() {
             self.current += 1;
         }
@@ -196,11 +172,69 @@ impl<'a> Parser<'a> {
         matches!(self.peek(), Lexeme::Eof)
     }
 
-    fn peek(&self) -> &Lexeme {
-        &self.tokens[self.current]
+    fn peek(&self) -> &Lexeme<'a> {
+        &self.tokens[self.current].lexeme
+    }
+
+    fn previous(&self) -> &Lexeme<'a> {
+        &self.tokens[self.current - 1].lexeme
     }
 
-    fn previous(&self) -> &Lexeme {
-        &self.tokens[self.current - 1]
+    /// Span of the token currently under the cursor.
+    fn peek_span(&self) -> Span {
+        self.tokens[self.current].span
+    }
+
+    /// Span of the most recently consumed token.
+    fn previous_span(&self) -> Span {
+        self.tokens[self.current - 1].span
+    }
+}
+
+/// Left/right binding powers for infix operators, or `None` if the lexeme is
+/// not an infix operator. Left-associative operators encode as `(n, n + 1)`;
+/// right-associative ones would encode as `(n + 1, n)`.
+fn infix_binding_power(lexeme: &Lexeme<'_>) -> Option<(u8, u8)> {
+    let bp = match lexeme {
+        Lexeme::EqualEqual | Lexeme::BangEqual => (1, 2),
+        Lexeme::Less | Lexeme::LessEqual | Lexeme::Greater | Lexeme::GreaterEqual => (3, 4),
+        Lexeme::Operator(MathOp::Plus) | Lexeme::Operator(MathOp::Minus) => (5, 6),
+        Lexeme::Operator(MathOp::Star) | Lexeme::Operator(MathOp::Slash) => (7, 8),
+        _ => return None,
+    };
+    Some(bp)
+}
+
+/// Right binding power for the prefix operators `!` and unary `-`, which bind
+/// tighter than any infix operator.
+fn prefix_binding_power(lexeme: &Lexeme<'_>) -> ((), u8) {
+    match lexeme {
+        Lexeme::Bang | Lexeme::Operator(MathOp::Minus) => ((), 9),
+        _ => unreachable!("prefix_binding_power called on a non-prefix operator"),
+    }
+}
+
+/// Keywords that begin a statement and therefore make good synchronization
+/// points during error recovery.
+fn is_statement_start(keyword: &str) -> bool {
+    matches!(
+        keyword,
+        "class" | "fun" | "let" | "var" | "for" | "if" | "while" | "return" | "print"
+    )
+}
+
+fn infix_op(lexeme: &Lexeme<'_>) -> BinaryOp {
+    match lexeme {
+        Lexeme::EqualEqual => BinaryOp::Equal,
+        Lexeme::BangEqual => BinaryOp::NotEqual,
+        Lexeme::Less => BinaryOp::Less,
+        Lexeme::LessEqual => BinaryOp::LessEqual,
+        Lexeme::Greater => BinaryOp::Greater,
+        Lexeme::GreaterEqual => BinaryOp::GreaterEqual,
+        Lexeme::Operator(MathOp::Plus) => BinaryOp::Add,
+        Lexeme::Operator(MathOp::Minus) => BinaryOp::Subtract,
+        Lexeme::Operator(MathOp::Star) => BinaryOp::Multiply,
+        Lexeme::Operator(MathOp::Slash) => BinaryOp::Divide,
+        _ => unreachable!("infix_op called on a non-infix operator"),
     }
 }
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use crate::lexeme::Lexeme;
+
+/// A source location: the line a token starts on and a half-open column range.
+///
+/// Columns are 1-based; `start_col..end_col` is exclusive of `end_col`, so a
+/// single-character token starting at column `c` has `end_col == c + 1`. For a
+/// token that runs over several lines (a multi-line string), `line` and
+/// `start_col` refer to its first line and `end_col` to its last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, start_col: usize, end_col: usize) -> Self {
+        Span {
+            line,
+            start_col,
+            end_col,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[line {}, col {}]", self.line, self.start_col)
+    }
+}
+
+/// A lexeme paired with the source span it was scanned from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token<'a> {
+    pub lexeme: Lexeme<'a>,
+    pub span: Span,
+}
+
+impl<'a> Token<'a> {
+    pub fn new(lexeme: Lexeme<'a>, span: Span) -> Self {
+        Token { lexeme, span }
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lexeme)
+    }
+}
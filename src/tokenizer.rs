@@ -1,32 +1,44 @@
 use crate::lexeme::{Lexeme, MathOp};
+use crate::token::{Span, Token};
 
 pub struct Tokenizer<'a> {
-    tokens: Vec<Lexeme>,
-    // lifetime of the input &str. chars should never live longer than the input from which they were created
-    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    // the source the tokens borrow their slices from
+    input: &'a str,
+    tokens: Vec<Token<'a>>,
+    // byte offset of the next character to scan
+    pos: usize,
     line: usize,
+    col: usize,
+    // byte offset / position where the token currently being scanned began
+    start: usize,
+    start_line: usize,
+    start_col: usize,
     has_error: bool,
 }
 
 impl<'a> Tokenizer<'a> {
     pub fn new(input: &'a str) -> Self {
         Tokenizer {
+            input,
             tokens: Vec::new(),
-            chars: input.chars().peekable(),
+            pos: 0,
             line: 1,
+            col: 1,
+            start: 0,
+            start_line: 1,
+            start_col: 1,
             has_error: false,
         }
     }
 
-    pub fn tokenize(&mut self) -> Result<&[Lexeme], &[Lexeme]> {
-        while let Some(&c) = self.chars.peek() {
+    pub fn tokenize(&mut self) -> Result<&[Token<'a>], &[Token<'a>]> {
+        while let Some(c) = self.peek() {
+            self.start = self.pos;
+            self.start_line = self.line;
+            self.start_col = self.col;
             match c {
-                ' ' | '\t' | '\r' => {
-                    self.chars.next();
-                }
-                '\n' => {
-                    self.chars.next();
-                    self.line += 1;
+                ' ' | '\t' | '\r' | '\n' => {
+                    self.bump();
                 }
                 '(' => self.add_token(Lexeme::LeftParen),
                 ')' => self.add_token(Lexeme::RightParen),
@@ -47,14 +59,16 @@ impl<'a> Tokenizer<'a> {
                 '*' => self.add_token(Lexeme::Operator(MathOp::Star)),
                 '/' => self.handle_slash(),
                 _ => {
-                    self.tokens.push(Lexeme::UnexpectedCharError(self.line, c));
+                    self.bump();
+                    self.emit(Lexeme::UnexpectedCharError(self.line, c));
                     self.has_error = true;
-                    self.chars.next();
                 }
             }
         }
 
-        self.tokens.push(Lexeme::Eof);
+        self.start_line = self.line;
+        self.start_col = self.col;
+        self.emit(Lexeme::Eof);
 
         if self.has_error {
             Err(&self.tokens)
@@ -63,141 +77,286 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
-    fn add_token(&mut self, lexeme: Lexeme) {
-        self.tokens.push(lexeme);
-        self.chars.next();
+    /// The next character without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    /// The character `n` positions ahead of the cursor without consuming.
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.input[self.pos..].chars().nth(n)
+    }
+
+    /// Consumes the next character, keeping `pos`/`line`/`col` in sync.
+    fn bump(&mut self) -> Option<char> {
+        let c = self.input[self.pos..].chars().next()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    /// Pushes a lexeme spanning from the recorded token start to the current
+    /// position.
+    fn emit(&mut self, lexeme: Lexeme<'a>) {
+        let span = Span::new(self.start_line, self.start_col, self.col);
+        self.tokens.push(Token::new(lexeme, span));
+    }
+
+    fn add_token(&mut self, lexeme: Lexeme<'a>) {
+        self.bump();
+        self.emit(lexeme);
     }
 
     fn handle_equal(&mut self) {
-        self.chars.next();
-        if let Some(&'=') = self.chars.peek() {
-            self.add_token(Lexeme::EqualEqual);
+        self.bump();
+        if self.peek() == Some('=') {
+            self.bump();
+            self.emit(Lexeme::EqualEqual);
         } else {
-            self.tokens.push(Lexeme::Equal);
+            self.emit(Lexeme::Equal);
         }
     }
 
     fn handle_bang(&mut self) {
-        self.chars.next();
-        if let Some(&'=') = self.chars.peek() {
-            self.add_token(Lexeme::BangEqual);
+        self.bump();
+        if self.peek() == Some('=') {
+            self.bump();
+            self.emit(Lexeme::BangEqual);
         } else {
-            self.tokens.push(Lexeme::Bang);
+            self.emit(Lexeme::Bang);
         }
     }
 
     fn handle_less(&mut self) {
-        self.chars.next();
-        if let Some(&'=') = self.chars.peek() {
-            self.add_token(Lexeme::LessEqual);
+        self.bump();
+        if self.peek() == Some('=') {
+            self.bump();
+            self.emit(Lexeme::LessEqual);
         } else {
-            self.tokens.push(Lexeme::Less);
+            self.emit(Lexeme::Less);
         }
     }
 
     fn handle_greater(&mut self) {
-        self.chars.next();
-        if let Some(&'=') = self.chars.peek() {
-            self.add_token(Lexeme::GreaterEqual);
+        self.bump();
+        if self.peek() == Some('=') {
+            self.bump();
+            self.emit(Lexeme::GreaterEqual);
         } else {
-            self.tokens.push(Lexeme::Greater);
+            self.emit(Lexeme::Greater);
         }
     }
 
     fn handle_number(&mut self) {
-        let mut number = String::new();
+        // Hexadecimal integer literal: `0x1F`.
+        if self.peek() == Some('0') && matches!(self.peek_nth(1), Some('x') | Some('X')) {
+            return self.handle_hex_number();
+        }
+
         let mut has_decimal = false;
-        while let Some(&d) = self.chars.peek() {
+        while let Some(d) = self.peek() {
             match d {
                 '0'..='9' => {
-                    number.push(d);
-                    self.chars.next();
+                    self.bump();
                 }
                 '.' if !has_decimal => {
-                    if self
-                        .chars
-                        .clone()
-                        .nth(1)
-                        .map_or(false, |next| next.is_ascii_digit())
-                    {
-                        number.push(d);
+                    if self.peek_nth(1).map_or(false, |next| next.is_ascii_digit()) {
                         has_decimal = true;
-                        self.chars.next();
+                        self.bump();
                     } else {
                         break;
                     }
                 }
+                'e' | 'E' if self.exponent_follows() => {
+                    self.bump();
+                    if matches!(self.peek(), Some('+') | Some('-')) {
+                        self.bump();
+                    }
+                    while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+                        self.bump();
+                    }
+                    break;
+                }
                 _ => break,
             }
         }
 
-        let n = number.parse().unwrap();
-        self.tokens.push(Lexeme::Number(number, n));
+        let number = &self.input[self.start..self.pos];
+        match number.parse() {
+            Ok(n) => self.emit(Lexeme::Number(number, n)),
+            Err(_) => {
+                self.emit(Lexeme::MalformedNumberError(self.line, number.to_string()));
+                self.has_error = true;
+                return;
+            }
+        }
+
+        if self.peek() == Some('.') {
+            self.start = self.pos;
+            self.start_line = self.line;
+            self.start_col = self.col;
+            self.bump();
+            self.emit(Lexeme::Dot);
+        }
+    }
+
+    /// Whether the upcoming `e`/`E` introduces a well-formed exponent, i.e. an
+    /// optional sign followed by at least one digit.
+    fn exponent_follows(&self) -> bool {
+        match self.peek_nth(1) {
+            Some('+') | Some('-') => self.peek_nth(2).map_or(false, |c| c.is_ascii_digit()),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    fn handle_hex_number(&mut self) {
+        self.bump(); // `0`
+        self.bump(); // `x` / `X`
+        while self.peek().map_or(false, |c| c.is_ascii_hexdigit()) {
+            self.bump();
+        }
 
-        if let Some(&'.') = self.chars.peek() {
-            self.tokens.push(Lexeme::Dot);
-            self.chars.next();
+        let original = &self.input[self.start..self.pos];
+        match i64::from_str_radix(&original[2..], 16) {
+            Ok(value) => self.emit(Lexeme::Number(original, value as f64)),
+            Err(_) => {
+                self.emit(Lexeme::MalformedNumberError(self.line, original.to_string()));
+                self.has_error = true;
+            }
         }
     }
 
     fn handle_identifier(&mut self) {
-        let mut identifier = String::new();
-        while let Some(&d) = self.chars.peek() {
+        while let Some(d) = self.peek() {
             if d.is_alphanumeric() || d == '_' {
-                identifier.push(d);
-                self.chars.next();
+                self.bump();
             } else {
                 break;
             }
         }
 
-        match identifier.as_str() {
+        let identifier = &self.input[self.start..self.pos];
+        match identifier {
             "and" | "class" | "else" | "false" | "for" | "fun" | "if" | "let" | "nil" | "or"
             | "return" | "super" | "this" | "true" | "var" | "while" | "print" => {
-                self.tokens.push(Lexeme::Keyword(identifier));
+                self.emit(Lexeme::Keyword(identifier));
             }
             _ => {
-                self.tokens.push(Lexeme::Identifier(identifier));
+                self.emit(Lexeme::Identifier(identifier));
             }
         }
     }
 
     fn handle_string(&mut self) {
-        self.chars.next();
-        let mut string = String::new();
-        let start_line = self.line;
+        self.bump(); // opening quote
+        let content_start = self.pos;
+        // `value` accumulates the decoded characters; `content_end` marks the
+        // source slice preserved for the `STRING "..."` output.
+        let mut value = String::new();
+        let mut content_end = self.pos;
         let mut terminated = false;
 
-        while let Some(&d) = self.chars.peek() {
-            if d == '"' {
-                self.chars.next();
-                terminated = true;
-                break;
-            } else if d == '\n' {
-                self.line += 1;
+        while let Some(d) = self.peek() {
+            match d {
+                '"' => {
+                    content_end = self.pos;
+                    self.bump();
+                    terminated = true;
+                    break;
+                }
+                '\\' => {
+                    self.bump();
+                    match self.decode_escape() {
+                        Ok(c) => value.push(c),
+                        Err(msg) => {
+                            self.emit(Lexeme::MalformedEscapeError(self.line, msg));
+                            self.has_error = true;
+                            return;
+                        }
+                    }
+                }
+                _ => {
+                    value.push(d);
+                    self.bump();
+                }
             }
-            string.push(d);
-            self.chars.next();
         }
 
         if terminated {
-            self.tokens.push(Lexeme::String(string));
+            let original = &self.input[content_start..content_end];
+            self.emit(Lexeme::String(original, value));
         } else {
-            self.tokens
-                .push(Lexeme::UnterminatedStringError(start_line));
+            self.emit(Lexeme::UnterminatedStringError(self.start_line));
             self.has_error = true;
         }
     }
 
+    /// Decodes a single escape sequence (the leading `\` is already consumed)
+    /// and returns the decoded character.
+    fn decode_escape(&mut self) -> std::result::Result<char, String> {
+        match self.peek() {
+            Some('n') => self.take_escape('\n'),
+            Some('t') => self.take_escape('\t'),
+            Some('r') => self.take_escape('\r'),
+            Some('\\') => self.take_escape('\\'),
+            Some('"') => self.take_escape('"'),
+            Some('0') => self.take_escape('\0'),
+            Some('u') => {
+                self.bump();
+                self.decode_unicode_escape()
+            }
+            Some(c) => Err(format!("Invalid escape sequence: \\{}", c)),
+            None => Err("Unterminated escape sequence.".to_string()),
+        }
+    }
+
+    fn take_escape(&mut self, decoded: char) -> std::result::Result<char, String> {
+        self.bump();
+        Ok(decoded)
+    }
+
+    /// Decodes a `\u{XXXX}` escape, with the leading `\u` already consumed.
+    fn decode_unicode_escape(&mut self) -> std::result::Result<char, String> {
+        if self.peek() != Some('{') {
+            return Err("Malformed unicode escape: expected '{'.".to_string());
+        }
+        self.bump();
+
+        let mut hex = String::new();
+        loop {
+            match self.peek() {
+                Some('}') => {
+                    self.bump();
+                    break;
+                }
+                Some(c) if c.is_ascii_hexdigit() => {
+                    hex.push(c);
+                    self.bump();
+                }
+                _ => return Err("Malformed unicode escape: expected hex digits.".to_string()),
+            }
+        }
+
+        let code = u32::from_str_radix(&hex, 16)
+            .map_err(|_| "Malformed unicode escape: invalid code point.".to_string())?;
+        char::from_u32(code).ok_or_else(|| "Malformed unicode escape: invalid code point.".to_string())
+    }
+
     fn handle_slash(&mut self) {
-        if let Some('/') = self.chars.clone().nth(1) {
-            self.chars.next();
-            self.chars.next();
-            while let Some(&d) = self.chars.peek() {
+        if self.peek_nth(1) == Some('/') {
+            self.bump();
+            self.bump();
+            while let Some(d) = self.peek() {
                 if d == '\n' {
                     break;
                 }
-                self.chars.next();
+                self.bump();
             }
         } else {
             self.add_token(Lexeme::Operator(MathOp::Slash));